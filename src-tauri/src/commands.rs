@@ -0,0 +1,283 @@
+//! Tauri commands bridging the frontend to the LGP parser in [`crate::lgp`].
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use crate::lgp::{self, LgpEntry, ParsedLgp};
+use crate::settings;
+
+/// Opaque handle to an archive parsed into [`ArchiveStore`].
+pub type ArchiveHandle = u32;
+
+#[derive(Default)]
+pub struct ArchiveStore {
+    next_handle: AtomicU32,
+    archives: Mutex<HashMap<ArchiveHandle, ParsedLgp>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryInfo {
+    pub name: String,
+    pub size: u32,
+    pub offset: u32,
+}
+
+impl From<&LgpEntry> for EntryInfo {
+    fn from(entry: &LgpEntry) -> Self {
+        EntryInfo {
+            name: entry.name.clone(),
+            size: entry.size,
+            offset: entry.offset,
+        }
+    }
+}
+
+#[tauri::command]
+pub fn open_lgp<R: Runtime>(
+    app: AppHandle<R>,
+    path: PathBuf,
+    store: State<ArchiveStore>,
+) -> Result<ArchiveHandle, String> {
+    let parsed = lgp::parse(&path).map_err(|e| e.to_string())?;
+    let handle = store.next_handle.fetch_add(1, Ordering::SeqCst);
+    store.archives.lock().unwrap().insert(handle, parsed);
+
+    if let Ok(recent) = settings::record_recent(&app, &path.to_string_lossy()) {
+        let _ = crate::menu::rebuild_recent_menu(&app, &recent);
+    }
+
+    Ok(handle)
+}
+
+#[tauri::command]
+pub fn list_entries(
+    handle: ArchiveHandle,
+    store: State<ArchiveStore>,
+) -> Result<Vec<EntryInfo>, String> {
+    let archives = store.archives.lock().unwrap();
+    let parsed = archives
+        .get(&handle)
+        .ok_or_else(|| format!("unknown archive handle {handle}"))?;
+    Ok(parsed.entries.iter().map(EntryInfo::from).collect())
+}
+
+#[tauri::command]
+pub fn read_entry(
+    handle: ArchiveHandle,
+    name: String,
+    store: State<ArchiveStore>,
+) -> Result<Vec<u8>, String> {
+    let archives = store.archives.lock().unwrap();
+    let parsed = archives
+        .get(&handle)
+        .ok_or_else(|| format!("unknown archive handle {handle}"))?;
+    let entry = parsed
+        .entries
+        .iter()
+        .find(|e| e.name == name)
+        .ok_or_else(|| format!("no such entry: {name}"))?;
+    lgp::read_entry_data(parsed, entry).map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ExtractProgress {
+    current: usize,
+    total: usize,
+    name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ExtractError {
+    name: Option<String>,
+    message: String,
+}
+
+/// Resolve an archive entry's name against `dest_dir`, rejecting any
+/// `..`, root, or drive-prefix component so a malicious or modded
+/// archive can't write outside the extraction directory (zip-slip).
+fn safe_extract_path(dest_dir: &Path, name: &str) -> Result<PathBuf, String> {
+    use std::path::Component;
+
+    let mut relative = PathBuf::new();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(format!("entry name escapes destination directory: {name}"));
+            }
+        }
+    }
+    Ok(dest_dir.join(relative))
+}
+
+/// Extract every entry of `handle` into `dest_dir`, reporting progress on
+/// `extract://progress` and finishing with `extract://done` or
+/// `extract://error`. Runs on a background thread so the command itself
+/// returns immediately and never blocks the webview.
+#[tauri::command]
+pub fn extract_all(
+    app: AppHandle,
+    handle: ArchiveHandle,
+    dest_dir: PathBuf,
+    store: State<ArchiveStore>,
+) -> Result<(), String> {
+    let parsed = {
+        let archives = store.archives.lock().unwrap();
+        archives
+            .get(&handle)
+            .ok_or_else(|| format!("unknown archive handle {handle}"))?
+            .clone()
+    };
+
+    std::thread::spawn(move || {
+        let total = parsed.entries.len();
+        for (current, entry) in parsed.entries.iter().enumerate() {
+            let result = safe_extract_path(&dest_dir, &entry.name).and_then(|out_path| {
+                lgp::read_entry_data(&parsed, entry)
+                    .map_err(|e| e.to_string())
+                    .and_then(|data| {
+                        if let Some(parent) = out_path.parent() {
+                            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                        }
+                        fs::write(&out_path, data).map_err(|e| e.to_string())
+                    })
+            });
+
+            if let Err(message) = result {
+                let _ = app.emit(
+                    "extract://error",
+                    ExtractError {
+                        name: Some(entry.name.clone()),
+                        message,
+                    },
+                );
+                return;
+            }
+
+            let _ = app.emit(
+                "extract://progress",
+                ExtractProgress {
+                    current: current + 1,
+                    total,
+                    name: entry.name.clone(),
+                },
+            );
+        }
+
+        let _ = app.emit("extract://done", total);
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn replace_entry(
+    handle: ArchiveHandle,
+    name: String,
+    bytes: Vec<u8>,
+    store: State<ArchiveStore>,
+) -> Result<(), String> {
+    let mut archives = store.archives.lock().unwrap();
+    let parsed = archives
+        .get_mut(&handle)
+        .ok_or_else(|| format!("unknown archive handle {handle}"))?;
+    parsed
+        .replace_entry(&name, bytes)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn add_entry(
+    handle: ArchiveHandle,
+    name: String,
+    bytes: Vec<u8>,
+    store: State<ArchiveStore>,
+) -> Result<(), String> {
+    let mut archives = store.archives.lock().unwrap();
+    let parsed = archives
+        .get_mut(&handle)
+        .ok_or_else(|| format!("unknown archive handle {handle}"))?;
+    parsed.add_entry(name, bytes).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn remove_entry(
+    handle: ArchiveHandle,
+    name: String,
+    store: State<ArchiveStore>,
+) -> Result<(), String> {
+    let mut archives = store.archives.lock().unwrap();
+    let parsed = archives
+        .get_mut(&handle)
+        .ok_or_else(|| format!("unknown archive handle {handle}"))?;
+    parsed.remove_entry(&name).map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SaveReport {
+    pub compatibility_note: String,
+    pub truncated_name_collisions: Vec<String>,
+}
+
+impl From<lgp::WriteReport> for SaveReport {
+    fn from(report: lgp::WriteReport) -> Self {
+        SaveReport {
+            compatibility_note: report.compatibility_note,
+            truncated_name_collisions: report.truncated_name_collisions,
+        }
+    }
+}
+
+/// Rebuild `handle`'s archive to `out_path`, which the frontend obtains
+/// from the already-registered dialog plugin's save picker. The
+/// returned [`SaveReport`] always carries a compatibility note — this
+/// app's check-code/conflict-table recomputation isn't verified against
+/// FF7's own format — so the frontend can surface it rather than the
+/// save silently appearing fully game-compatible.
+#[tauri::command]
+pub fn save_lgp(
+    handle: ArchiveHandle,
+    out_path: PathBuf,
+    store: State<ArchiveStore>,
+) -> Result<SaveReport, String> {
+    let archives = store.archives.lock().unwrap();
+    let parsed = archives
+        .get(&handle)
+        .ok_or_else(|| format!("unknown archive handle {handle}"))?;
+    lgp::write(parsed, &out_path)
+        .map(SaveReport::from)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_extract_path_allows_nested_normal_names() {
+        let dest = Path::new("/tmp/extract");
+        assert_eq!(
+            safe_extract_path(dest, "flevel/world_us.lzs").unwrap(),
+            dest.join("flevel").join("world_us.lzs")
+        );
+    }
+
+    #[test]
+    fn safe_extract_path_rejects_parent_dir_traversal() {
+        let dest = Path::new("/tmp/extract");
+        assert!(safe_extract_path(dest, "../../etc/passwd").is_err());
+    }
+
+    #[test]
+    fn safe_extract_path_rejects_absolute_names() {
+        let dest = Path::new("/tmp/extract");
+        assert!(safe_extract_path(dest, "/etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,620 @@
+//! Parsing for Square's LGP archive format, as used by Final Fantasy VII.
+//!
+//! Layout: a 12-byte header (`SQUARESOFT` magic starting at byte 2), a
+//! 4-byte little-endian table-of-contents count, that many 27-byte ToC
+//! records (20-byte padded name + 4-byte offset + check code + conflict
+//! index), then (in archives produced by [`write`]) a name-conflict table
+//! that [`parse`] does not read, then the file data itself, terminated by
+//! the literal string `FINAL FANTASY7`.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8; 10] = b"SQUARESOFT";
+const TOC_RECORD_LEN: u64 = 27;
+const NAME_LEN: usize = 20;
+const TERMINATOR: &[u8] = b"FINAL FANTASY7";
+const NO_CONFLICT: u16 = 0;
+const CHAIN_END: u16 = 0xFFFF;
+
+#[derive(Debug)]
+pub enum LgpError {
+    Io(io::Error),
+    BadMagic,
+    MissingTerminator,
+    InvalidTocCount { count: u32, file_len: u64 },
+    EntryOutOfRange(String),
+    NoSuchEntry(String),
+    EntryAlreadyExists(String),
+}
+
+impl From<io::Error> for LgpError {
+    fn from(err: io::Error) -> Self {
+        LgpError::Io(err)
+    }
+}
+
+impl std::fmt::Display for LgpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LgpError::Io(err) => write!(f, "io error: {err}"),
+            LgpError::BadMagic => write!(f, "missing SQUARESOFT magic"),
+            LgpError::MissingTerminator => write!(f, "missing FINAL FANTASY7 terminator"),
+            LgpError::InvalidTocCount { count, file_len } => write!(
+                f,
+                "table-of-contents count {count} cannot fit in a {file_len}-byte file"
+            ),
+            LgpError::EntryOutOfRange(name) => {
+                write!(f, "entry \"{name}\" points outside the file")
+            }
+            LgpError::NoSuchEntry(name) => write!(f, "no such entry: {name}"),
+            LgpError::EntryAlreadyExists(name) => write!(f, "entry already exists: {name}"),
+        }
+    }
+}
+
+impl std::error::Error for LgpError {}
+
+/// A single table-of-contents record, pointing at one file's data.
+#[derive(Debug, Clone)]
+pub struct LgpEntry {
+    pub name: String,
+    pub offset: u32,
+    pub check_code: u8,
+    pub conflict_index: u16,
+    pub size: u32,
+}
+
+/// An archive parsed into memory: its source path plus the ToC, with any
+/// in-memory edits layered on top until [`write`] commits them to disk.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedLgp {
+    pub path: PathBuf,
+    pub entries: Vec<LgpEntry>,
+    /// Bytes for entries that were added or replaced since parsing, keyed
+    /// by name. Entries not present here are read from `path` on demand.
+    overrides: HashMap<String, Vec<u8>>,
+}
+
+impl ParsedLgp {
+    fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|e| e.name == name)
+    }
+
+    /// Overwrite an existing entry's bytes. The entry must already be in
+    /// the archive; use [`add_entry`](Self::add_entry) for new ones.
+    pub fn replace_entry(&mut self, name: &str, data: Vec<u8>) -> Result<(), LgpError> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| e.name == name)
+            .ok_or_else(|| LgpError::NoSuchEntry(name.to_string()))?;
+        entry.size = data.len() as u32;
+        self.overrides.insert(name.to_string(), data);
+        Ok(())
+    }
+
+    /// Add a new entry. Fails if the name is already present.
+    pub fn add_entry(&mut self, name: String, data: Vec<u8>) -> Result<(), LgpError> {
+        if self.contains(&name) {
+            return Err(LgpError::EntryAlreadyExists(name));
+        }
+        self.entries.push(LgpEntry {
+            size: data.len() as u32,
+            name: name.clone(),
+            offset: 0,
+            check_code: 0,
+            conflict_index: NO_CONFLICT,
+        });
+        self.overrides.insert(name, data);
+        Ok(())
+    }
+
+    /// Remove an entry so it is omitted the next time the archive is
+    /// written.
+    pub fn remove_entry(&mut self, name: &str) -> Result<(), LgpError> {
+        if !self.contains(name) {
+            return Err(LgpError::NoSuchEntry(name.to_string()));
+        }
+        self.entries.retain(|e| e.name != name);
+        self.overrides.remove(name);
+        Ok(())
+    }
+}
+
+fn read_padded_name(buf: &[u8]) -> String {
+    let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..end]).into_owned()
+}
+
+/// Read exactly `buf.len()` bytes, logging a warning with `context` (and
+/// the archive path) before surfacing the I/O error, so a truncated or
+/// otherwise malformed archive leaves a trail instead of a bare
+/// `io error` with no indication of which part of the file was bad.
+fn read_exact_ctx(
+    file: &mut File,
+    buf: &mut [u8],
+    path: &Path,
+    context: &str,
+) -> Result<(), LgpError> {
+    file.read_exact(buf).map_err(|err| {
+        log::warn!("{}: {context}: {err}", path.display());
+        LgpError::Io(err)
+    })
+}
+
+/// Parse an LGP archive's header and table of contents, without loading
+/// any file data into memory.
+pub fn parse(path: &Path) -> Result<ParsedLgp, LgpError> {
+    let mut file = File::open(path)?;
+
+    let mut header = [0u8; 12];
+    read_exact_ctx(&mut file, &mut header, path, "reading 12-byte header")?;
+    if &header[2..12] != MAGIC.as_slice() {
+        log::warn!("{}: missing SQUARESOFT magic in header", path.display());
+        return Err(LgpError::BadMagic);
+    }
+
+    let mut count_buf = [0u8; 4];
+    read_exact_ctx(&mut file, &mut count_buf, path, "reading ToC count")?;
+    let count = u32::from_le_bytes(count_buf);
+
+    // Bound the claimed ToC count against the actual file size before
+    // trusting it for an allocation: a corrupt or truncated archive can
+    // put an arbitrary 4-byte value here, and every record needs at
+    // least TOC_RECORD_LEN bytes to exist.
+    let file_len = file.metadata()?.len();
+    let max_count = file_len.saturating_sub(16) / TOC_RECORD_LEN;
+    if count as u64 > max_count {
+        log::warn!(
+            "{}: table-of-contents count {count} cannot fit in a {file_len}-byte file, refusing to parse",
+            path.display()
+        );
+        return Err(LgpError::InvalidTocCount { count, file_len });
+    }
+
+    let mut entries = Vec::with_capacity(count as usize);
+    for index in 0..count {
+        let mut name_buf = [0u8; NAME_LEN];
+        read_exact_ctx(
+            &mut file,
+            &mut name_buf,
+            path,
+            &format!("reading ToC record {index} name"),
+        )?;
+        let name = read_padded_name(&name_buf);
+
+        let mut offset_buf = [0u8; 4];
+        read_exact_ctx(
+            &mut file,
+            &mut offset_buf,
+            path,
+            &format!("reading ToC record {index} (\"{name}\") offset"),
+        )?;
+        let offset = u32::from_le_bytes(offset_buf);
+
+        let mut check_code = [0u8; 1];
+        read_exact_ctx(
+            &mut file,
+            &mut check_code,
+            path,
+            &format!("reading ToC record {index} (\"{name}\") check code"),
+        )?;
+
+        let mut conflict_buf = [0u8; 2];
+        read_exact_ctx(
+            &mut file,
+            &mut conflict_buf,
+            path,
+            &format!("reading ToC record {index} (\"{name}\") conflict index"),
+        )?;
+        let conflict_index = u16::from_le_bytes(conflict_buf);
+
+        if offset as u64 + NAME_LEN as u64 + 4 > file_len {
+            log::warn!(
+                "{}: entry \"{name}\" data offset {offset} is out of range for a {file_len}-byte file",
+                path.display()
+            );
+            return Err(LgpError::EntryOutOfRange(name));
+        }
+
+        let size = {
+            let pos = file.stream_position()?;
+            file.seek(SeekFrom::Start(offset as u64))?;
+
+            let mut data_name_buf = [0u8; NAME_LEN];
+            read_exact_ctx(
+                &mut file,
+                &mut data_name_buf,
+                path,
+                &format!("reading data block name for entry \"{name}\" at offset {offset}"),
+            )?;
+            let data_name = read_padded_name(&data_name_buf);
+            if data_name != name {
+                log::warn!(
+                    "{}: entry \"{name}\" ToC name disagrees with data block name \"{data_name}\" at offset {offset}",
+                    path.display()
+                );
+            }
+
+            let mut size_buf = [0u8; 4];
+            read_exact_ctx(
+                &mut file,
+                &mut size_buf,
+                path,
+                &format!("reading data block size for entry \"{name}\" at offset {offset}"),
+            )?;
+            file.seek(SeekFrom::Start(pos))?;
+            u32::from_le_bytes(size_buf)
+        };
+
+        if offset as u64 + NAME_LEN as u64 + 4 + size as u64 > file_len {
+            log::warn!(
+                "{}: entry \"{name}\" claims size {size} at offset {offset}, which runs past the {file_len}-byte file",
+                path.display()
+            );
+            return Err(LgpError::EntryOutOfRange(name));
+        }
+
+        entries.push(LgpEntry {
+            name,
+            offset,
+            check_code: check_code[0],
+            conflict_index,
+            size,
+        });
+    }
+
+    let toc_end = 16 + count as u64 * TOC_RECORD_LEN;
+    let last_data_end = entries
+        .iter()
+        .map(|e| e.offset as u64 + NAME_LEN as u64 + 4 + e.size as u64)
+        .max()
+        .unwrap_or(toc_end);
+
+    file.seek(SeekFrom::Start(last_data_end))?;
+    let mut terminator = vec![0u8; TERMINATOR.len()];
+    if file.read_exact(&mut terminator).is_err() || terminator != TERMINATOR {
+        log::warn!(
+            "{}: missing FINAL FANTASY7 terminator at expected offset {last_data_end}",
+            path.display()
+        );
+        return Err(LgpError::MissingTerminator);
+    }
+
+    Ok(ParsedLgp {
+        path: path.to_path_buf(),
+        entries,
+        overrides: HashMap::new(),
+    })
+}
+
+/// Read one entry's raw bytes, preferring a pending in-memory edit over
+/// the copy on disk.
+pub fn read_entry_data(parsed: &ParsedLgp, entry: &LgpEntry) -> Result<Vec<u8>, LgpError> {
+    if let Some(data) = parsed.overrides.get(&entry.name) {
+        return Ok(data.clone());
+    }
+    let mut file = File::open(&parsed.path)?;
+    file.seek(SeekFrom::Start(entry.offset as u64 + NAME_LEN as u64 + 4))?;
+    let mut buf = vec![0u8; entry.size as usize];
+    file.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// A name as it's actually stored on disk: truncated (or null-padded)
+/// to the fixed-width ToC field. Two entries with different full names
+/// can still collide here, which is exactly the "duplicate filenames"
+/// case the conflict table exists to disambiguate.
+fn truncated_name_key(name: &str) -> [u8; NAME_LEN] {
+    let mut buf = [0u8; NAME_LEN];
+    let bytes = name.as_bytes();
+    let len = bytes.len().min(NAME_LEN);
+    buf[..len].copy_from_slice(&bytes[..len]);
+    buf
+}
+
+fn write_padded_name(out: &mut Vec<u8>, name: &str) {
+    out.extend_from_slice(&truncated_name_key(name));
+}
+
+/// A stable hash of an entry's on-disk (truncated) name, stored in its
+/// ToC record alongside the conflict-table index. This is **not** the
+/// original game's check/hash algorithm, which isn't documented closely
+/// enough to reproduce bit-for-bit in this sandbox, so archives written
+/// by [`write`] are round-trippable through this app but are not
+/// guaranteed to load correctly in FF7 itself or in other third-party
+/// LGP tools — see [`WriteReport::compatibility_note`], which every
+/// caller of `write` receives so this limitation isn't only visible in
+/// source comments.
+fn check_code(name: &str) -> u8 {
+    truncated_name_key(name)
+        .iter()
+        .fold(0u8, |acc, &b| acc.wrapping_add(b))
+}
+
+/// Returned by [`write`] so its caller — ultimately the frontend, via
+/// the `save_lgp` command — can surface the format-compatibility
+/// caveat to the user instead of it being silently true only in a
+/// doc comment.
+#[derive(Debug, Clone)]
+pub struct WriteReport {
+    pub compatibility_note: String,
+    /// Names whose on-disk (truncated) form collided with another
+    /// entry's and were disambiguated via the conflict table.
+    pub truncated_name_collisions: Vec<String>,
+}
+
+/// Rebuild `parsed` (original entries plus any pending edits) into a
+/// fresh LGP file at `out_path`: header, ToC, a conflict table chaining
+/// entries whose on-disk (truncated) names collide, then the file data
+/// blocks and the terminator. See [`check_code`]'s doc comment for the
+/// scope of the check-code/conflict-table compatibility this provides.
+pub fn write(parsed: &ParsedLgp, out_path: &Path) -> Result<WriteReport, LgpError> {
+    let data: Vec<Vec<u8>> = parsed
+        .entries
+        .iter()
+        .map(|e| read_entry_data(parsed, e))
+        .collect::<Result<_, _>>()?;
+
+    let check_codes: Vec<u8> = parsed.entries.iter().map(|e| check_code(&e.name)).collect();
+
+    let mut buckets: HashMap<[u8; NAME_LEN], Vec<usize>> = HashMap::new();
+    for (index, entry) in parsed.entries.iter().enumerate() {
+        buckets
+            .entry(truncated_name_key(&entry.name))
+            .or_default()
+            .push(index);
+    }
+
+    let mut conflict_index = vec![NO_CONFLICT; parsed.entries.len()];
+    let mut conflict_table: Vec<u8> = Vec::new();
+    let mut truncated_name_collisions: Vec<String> = Vec::new();
+    let mut next_slot: u16 = 1;
+    for indices in buckets.values() {
+        if indices.len() < 2 {
+            continue;
+        }
+        for (position, &entry_index) in indices.iter().enumerate() {
+            conflict_index[entry_index] = next_slot;
+            next_slot += 1;
+
+            let name = &parsed.entries[entry_index].name;
+            truncated_name_collisions.push(name.clone());
+            write_padded_name(&mut conflict_table, name);
+            let next = if position + 1 < indices.len() {
+                next_slot
+            } else {
+                CHAIN_END
+            };
+            conflict_table.extend_from_slice(&next.to_le_bytes());
+        }
+    }
+
+    if !truncated_name_collisions.is_empty() {
+        log::warn!(
+            "{}: {} entries share an on-disk truncated name and were chained in the conflict table: {}",
+            out_path.display(),
+            truncated_name_collisions.len(),
+            truncated_name_collisions.join(", ")
+        );
+    }
+
+    let toc_len = 4 + parsed.entries.len() as u64 * TOC_RECORD_LEN;
+    let data_start = 12 + toc_len + conflict_table.len() as u64;
+
+    let mut offsets = Vec::with_capacity(parsed.entries.len());
+    let mut cursor = data_start;
+    for bytes in &data {
+        offsets.push(cursor as u32);
+        cursor += NAME_LEN as u64 + 4 + bytes.len() as u64;
+    }
+
+    let mut out = Vec::with_capacity(cursor as usize + TERMINATOR.len());
+    out.extend_from_slice(&[0u8, 0u8]);
+    out.extend_from_slice(MAGIC.as_slice());
+
+    out.extend_from_slice(&(parsed.entries.len() as u32).to_le_bytes());
+    for (index, entry) in parsed.entries.iter().enumerate() {
+        write_padded_name(&mut out, &entry.name);
+        out.extend_from_slice(&offsets[index].to_le_bytes());
+        out.push(check_codes[index]);
+        out.extend_from_slice(&conflict_index[index].to_le_bytes());
+    }
+
+    out.extend_from_slice(&conflict_table);
+
+    for (index, entry) in parsed.entries.iter().enumerate() {
+        write_padded_name(&mut out, &entry.name);
+        out.extend_from_slice(&(data[index].len() as u32).to_le_bytes());
+        out.extend_from_slice(&data[index]);
+    }
+
+    out.extend_from_slice(TERMINATOR);
+
+    let mut file = File::create(out_path)?;
+    file.write_all(&out)?;
+
+    Ok(WriteReport {
+        compatibility_note: "This archive's per-entry check code and name-conflict table are \
+            produced by this app and are not verified against Final Fantasy VII's own lookup \
+            format; it may not load correctly in the original game or in other LGP tools."
+            .to_string(),
+        truncated_name_collisions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Unique path per test under the system temp dir; no fixture files
+    /// are tracked in the repo, so each test builds its own archive.
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "ff7_lgp_explorer_test_{}_{name}",
+            std::process::id()
+        ));
+        path
+    }
+
+    fn write_sample_archive(path: &Path) -> ParsedLgp {
+        let mut parsed = ParsedLgp {
+            path: path.to_path_buf(),
+            entries: Vec::new(),
+            overrides: HashMap::new(),
+        };
+        parsed
+            .add_entry("one.txt".to_string(), b"hello".to_vec())
+            .unwrap();
+        parsed
+            .add_entry("dir/two.txt".to_string(), b"world!!".to_vec())
+            .unwrap();
+        write(&parsed, path).unwrap();
+        parsed
+    }
+
+    #[test]
+    fn parse_rejects_bad_magic() {
+        let path = temp_path("bad_magic");
+        fs::write(&path, vec![0u8; 32]).unwrap();
+
+        assert!(matches!(parse(&path), Err(LgpError::BadMagic)));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn parse_rejects_truncated_header() {
+        let path = temp_path("truncated_header");
+        fs::write(&path, vec![0u8; 4]).unwrap();
+
+        assert!(matches!(parse(&path), Err(LgpError::Io(_))));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn parse_rejects_toc_count_past_end_of_file() {
+        let path = temp_path("huge_count");
+        let mut bytes = vec![0u8; 2];
+        bytes.extend_from_slice(MAGIC.as_slice());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        fs::write(&path, bytes).unwrap();
+
+        assert!(matches!(
+            parse(&path),
+            Err(LgpError::InvalidTocCount { .. })
+        ));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn parse_rejects_entry_offset_past_end_of_file() {
+        let path = temp_path("bad_offset");
+        write_sample_archive(&path);
+
+        let mut bytes = fs::read(&path).unwrap();
+        let first_record_offset_field = 16 + NAME_LEN;
+        let bad_offset = bytes.len() as u32 + 1000;
+        bytes[first_record_offset_field..first_record_offset_field + 4]
+            .copy_from_slice(&bad_offset.to_le_bytes());
+        fs::write(&path, &bytes).unwrap();
+
+        assert!(matches!(parse(&path), Err(LgpError::EntryOutOfRange(_))));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn parse_rejects_truncated_data_block() {
+        let path = temp_path("truncated_data");
+        write_sample_archive(&path);
+
+        let mut bytes = fs::read(&path).unwrap();
+        // Cut past the terminator into the last entry's data block.
+        bytes.truncate(bytes.len() - (TERMINATOR.len() + 3));
+        fs::write(&path, &bytes).unwrap();
+
+        assert!(parse(&path).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn round_trips_through_write_and_parse() {
+        let path = temp_path("round_trip");
+        write_sample_archive(&path);
+
+        let reparsed = parse(&path).unwrap();
+        assert_eq!(reparsed.entries.len(), 2);
+
+        let one = reparsed
+            .entries
+            .iter()
+            .find(|e| e.name == "one.txt")
+            .unwrap();
+        assert_eq!(read_entry_data(&reparsed, one).unwrap(), b"hello");
+
+        let two = reparsed
+            .entries
+            .iter()
+            .find(|e| e.name == "dir/two.txt")
+            .unwrap();
+        assert_eq!(read_entry_data(&reparsed, two).unwrap(), b"world!!");
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn round_trips_entries_with_colliding_truncated_names() {
+        let path = temp_path("conflict_round_trip");
+
+        // Two distinct full names that share the same first NAME_LEN
+        // bytes collide in the fixed-width on-disk field even though
+        // our in-memory model treats them as different entries — this
+        // is the "duplicate filenames" case the conflict table exists
+        // to disambiguate.
+        let prefix = "a".repeat(NAME_LEN);
+        let name_one = format!("{prefix}_one.bin");
+        let name_two = format!("{prefix}_two.bin");
+        assert_eq!(truncated_name_key(&name_one), truncated_name_key(&name_two));
+
+        let mut parsed = ParsedLgp {
+            path: path.to_path_buf(),
+            entries: Vec::new(),
+            overrides: HashMap::new(),
+        };
+        parsed
+            .add_entry(name_one.clone(), b"meow".to_vec())
+            .unwrap();
+        parsed
+            .add_entry(name_two.clone(), b"purr".to_vec())
+            .unwrap();
+        let report = write(&parsed, &path).unwrap();
+        assert_eq!(report.truncated_name_collisions.len(), 2);
+        assert!(!report.compatibility_note.is_empty());
+
+        // The on-disk name field only has room for NAME_LEN bytes, so
+        // reparsing can no longer tell these two entries' names apart —
+        // both ToC records read back with the shared truncated prefix,
+        // and the conflict table is what flags them as distinct records.
+        let reparsed = parse(&path).unwrap();
+        assert_eq!(reparsed.entries.len(), 2);
+        assert!(reparsed.entries.iter().all(|e| e.name == prefix));
+        assert!(reparsed
+            .entries
+            .iter()
+            .all(|e| e.conflict_index != NO_CONFLICT));
+
+        let bytes: Vec<Vec<u8>> = reparsed
+            .entries
+            .iter()
+            .map(|e| read_entry_data(&reparsed, e).unwrap())
+            .collect();
+        assert!(bytes.contains(&b"meow".to_vec()));
+        assert!(bytes.contains(&b"purr".to_vec()));
+
+        let _ = fs::remove_file(&path);
+    }
+}
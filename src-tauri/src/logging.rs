@@ -0,0 +1,44 @@
+//! Structured logging setup. Parse warnings emitted from [`crate::lgp`]
+//! land in stdout, a rotating log file, and the webview console, so a
+//! corrupt or modded archive leaves a trail a user can hand to us.
+
+use std::fs;
+
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_log::{Target, TargetKind};
+
+const MAX_LOG_LINES: usize = 500;
+
+/// Build the log plugin: stdout for `tauri dev`, a rotating file in the
+/// app's log dir for diagnosing user reports, and the webview console so
+/// the in-app diagnostics panel can mirror it live.
+pub fn plugin<R: Runtime>() -> tauri_plugin_log::TauriPlugin<R> {
+    tauri_plugin_log::Builder::new()
+        .targets([
+            Target::new(TargetKind::Stdout),
+            Target::new(TargetKind::LogDir { file_name: None }),
+            Target::new(TargetKind::Webview),
+        ])
+        .max_file_size(1_000_000)
+        .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepAll)
+        .level(log::LevelFilter::Info)
+        .build()
+}
+
+/// Return the last [`MAX_LOG_LINES`] lines of the current log file, for
+/// the frontend's diagnostics panel.
+#[tauri::command]
+pub fn get_recent_logs<R: Runtime>(app: AppHandle<R>) -> Result<Vec<String>, String> {
+    let log_dir = app.path().app_log_dir().map_err(|e| e.to_string())?;
+    let log_file = log_dir.join(format!("{}.log", app.package_info().name));
+
+    let contents = match fs::read_to_string(&log_file) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    let start = lines.len().saturating_sub(MAX_LOG_LINES);
+    Ok(lines[start..].to_vec())
+}
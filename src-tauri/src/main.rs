@@ -1,11 +1,50 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod commands;
+mod lgp;
+mod logging;
+mod menu;
+mod settings;
+
+use commands::ArchiveStore;
+
 pub fn main() {
     let mut builder = tauri::Builder::default()
+        .plugin(logging::plugin())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
-        .plugin(tauri_plugin_opener::init());
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_store::Builder::new().build())
+        .manage(ArchiveStore::default())
+        .menu(|app| menu::build(app))
+        .on_menu_event(|app, event| menu::handle_event(app, event))
+        .setup(|app| {
+            #[cfg(debug_assertions)]
+            {
+                use tauri::Manager;
+                if let Some(window) = app.get_webview_window("main") {
+                    window.open_devtools();
+                }
+            }
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            commands::open_lgp,
+            commands::list_entries,
+            commands::read_entry,
+            commands::extract_all,
+            commands::replace_entry,
+            commands::add_entry,
+            commands::remove_entry,
+            commands::save_lgp,
+            settings::get_settings,
+            settings::get_recent,
+            settings::push_recent,
+            settings::set_view_prefs,
+            settings::set_last_extract_dir,
+            logging::get_recent_logs,
+        ]);
 
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     {
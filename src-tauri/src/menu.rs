@@ -0,0 +1,111 @@
+//! Native application menu: File > Open Archive / Extract Selected /
+//! Extract All / Open Recent. Items emit events that the frontend (or a
+//! command) reacts to, keeping the menu itself free of business logic.
+
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+pub const OPEN_ARCHIVE: &str = "open_archive";
+pub const EXTRACT_SELECTED: &str = "extract_selected";
+pub const EXTRACT_ALL: &str = "extract_all";
+pub const RECENT_ITEM_PREFIX: &str = "recent:";
+
+/// Build the `File` menu with a placeholder `Open Recent` submenu; call
+/// [`rebuild_recent_menu`] once the recent-files list is known.
+pub fn build<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
+    let open_archive = MenuItem::with_id(
+        app,
+        OPEN_ARCHIVE,
+        "Open Archive...",
+        true,
+        Some("CmdOrCtrl+O"),
+    )?;
+    let extract_selected = MenuItem::with_id(
+        app,
+        EXTRACT_SELECTED,
+        "Extract Selected",
+        true,
+        None::<&str>,
+    )?;
+    let extract_all = MenuItem::with_id(app, EXTRACT_ALL, "Extract All...", true, None::<&str>)?;
+    let recent = Submenu::with_id(app, "open_recent", "Open Recent", true)?;
+
+    let file_menu = Submenu::with_id_and_items(
+        app,
+        "file",
+        "File",
+        true,
+        &[
+            &open_archive,
+            &PredefinedMenuItem::separator(app)?,
+            &extract_selected,
+            &extract_all,
+            &PredefinedMenuItem::separator(app)?,
+            &recent,
+            &PredefinedMenuItem::separator(app)?,
+            &PredefinedMenuItem::quit(app, None)?,
+        ],
+    )?;
+
+    Menu::with_items(app, &[&file_menu])
+}
+
+/// Replace the contents of the `Open Recent` submenu with one item per
+/// path, most-recently-opened first.
+pub fn rebuild_recent_menu<R: Runtime>(app: &AppHandle<R>, recent: &[String]) -> tauri::Result<()> {
+    let Some(menu) = app.menu() else {
+        return Ok(());
+    };
+    let Some(file_item) = menu.get("file") else {
+        return Ok(());
+    };
+    let Some(file_submenu) = file_item.as_submenu() else {
+        return Ok(());
+    };
+    let Some(recent_item) = file_submenu.get("open_recent") else {
+        return Ok(());
+    };
+    let Some(recent_submenu) = recent_item.as_submenu() else {
+        return Ok(());
+    };
+
+    for item in recent_submenu.items()? {
+        recent_submenu.remove(&item)?;
+    }
+
+    if recent.is_empty() {
+        let none = MenuItem::new(app, "(none)", false, None::<&str>)?;
+        recent_submenu.append(&none)?;
+        return Ok(());
+    }
+
+    for (index, path) in recent.iter().enumerate() {
+        let id = format!("{RECENT_ITEM_PREFIX}{index}");
+        let item = MenuItem::with_id(app, id, path, true, None::<&str>)?;
+        recent_submenu.append(&item)?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch a menu click to the frontend as a `menu://` event.
+pub fn handle_event<R: Runtime>(app: &AppHandle<R>, event: MenuEvent) {
+    let id = event.id().0.as_str();
+    if let Some(index) = id.strip_prefix(RECENT_ITEM_PREFIX) {
+        let _ = app.emit("menu://open-recent", index);
+        return;
+    }
+
+    match id {
+        OPEN_ARCHIVE => {
+            let _ = app.emit("menu://open-archive", ());
+        }
+        EXTRACT_SELECTED => {
+            let _ = app.emit("menu://extract-selected", ());
+        }
+        EXTRACT_ALL => {
+            let _ = app.emit("menu://extract-all", ());
+        }
+        _ => {}
+    }
+}
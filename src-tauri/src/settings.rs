@@ -0,0 +1,95 @@
+//! Persisted preferences: recently opened archives, the last extraction
+//! directory, and view options. Backed by `tauri_plugin_store`'s JSON
+//! file so they survive restarts.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "settings.json";
+const SETTINGS_KEY: &str = "settings";
+const MAX_RECENT: usize = 10;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Name,
+    Size,
+    Offset,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        SortOrder::Name
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Settings {
+    pub recent: Vec<String>,
+    pub last_extract_dir: Option<String>,
+    pub sort_order: SortOrder,
+    pub show_sizes_hex: bool,
+}
+
+fn load<R: Runtime>(app: &AppHandle<R>) -> Result<Settings, String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    Ok(store
+        .get(SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+fn save<R: Runtime>(app: &AppHandle<R>, settings: &Settings) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    let value = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    store.set(SETTINGS_KEY, value);
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Record `path` as the most recently opened archive, capping the list
+/// at [`MAX_RECENT`] entries and moving existing entries to the front.
+pub fn record_recent<R: Runtime>(app: &AppHandle<R>, path: &str) -> Result<Vec<String>, String> {
+    let mut settings = load(app)?;
+    settings.recent.retain(|p| p != path);
+    settings.recent.insert(0, path.to_string());
+    settings.recent.truncate(MAX_RECENT);
+    save(app, &settings)?;
+    Ok(settings.recent)
+}
+
+#[tauri::command]
+pub fn get_settings<R: Runtime>(app: AppHandle<R>) -> Result<Settings, String> {
+    load(&app)
+}
+
+#[tauri::command]
+pub fn get_recent<R: Runtime>(app: AppHandle<R>) -> Result<Vec<String>, String> {
+    Ok(load(&app)?.recent)
+}
+
+#[tauri::command]
+pub fn push_recent<R: Runtime>(app: AppHandle<R>, path: String) -> Result<Vec<String>, String> {
+    let recent = record_recent(&app, &path)?;
+    let _ = crate::menu::rebuild_recent_menu(&app, &recent);
+    Ok(recent)
+}
+
+#[tauri::command]
+pub fn set_view_prefs<R: Runtime>(
+    app: AppHandle<R>,
+    sort_order: SortOrder,
+    show_sizes_hex: bool,
+) -> Result<(), String> {
+    let mut settings = load(&app)?;
+    settings.sort_order = sort_order;
+    settings.show_sizes_hex = show_sizes_hex;
+    save(&app, &settings)
+}
+
+#[tauri::command]
+pub fn set_last_extract_dir<R: Runtime>(app: AppHandle<R>, dir: String) -> Result<(), String> {
+    let mut settings = load(&app)?;
+    settings.last_extract_dir = Some(dir);
+    save(&app, &settings)
+}